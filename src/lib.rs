@@ -21,9 +21,22 @@
 //! [map]: trait.Logger.html#method.map
 //! [filter]: trait.Logger.html#method.filter
 
+#[cfg(feature = "log")]
+pub mod log_bridge;
+
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::convert::Infallible;
 use std::iter;
 use std::marker::PhantomData;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::sync::mpsc::SyncSender;
+use std::sync::mpsc::TrySendError;
+use std::thread;
 
 /// A logger is a routine that takes input and has side-effects.
 ///
@@ -57,6 +70,14 @@ pub trait Logger<I>
         Filter{inner: self, f}
     }
 
+    /// Attach a mutable severity-level threshold to this logger,
+    /// with optional per-context overrides.
+    fn leveled(self, global: Level) -> Leveled<Self>
+        where Self: Sized
+    {
+        Leveled{inner: self, global: Cell::new(global), contexts: RefCell::new(HashMap::new())}
+    }
+
     /// Apply a function to each input
     /// before passing it to the logger.
     fn map<F, B>(self, f: F) -> Map<Self, F, B>
@@ -65,6 +86,14 @@ pub trait Logger<I>
         Map{inner: self, f, _phantom: PhantomData}
     }
 
+    /// Apply a function to this logger's error type,
+    /// producing a logger with a different `Error` type.
+    fn map_err<F, E>(self, f: F) -> MapErr<Self, F>
+        where Self: Sized, F: FnMut(Self::Error) -> E
+    {
+        MapErr{inner: self, f}
+    }
+
     /// Return a logger that
     /// silently drops errors reported by this logger.
     fn safe<E>(self) -> Safe<Self, E>
@@ -72,9 +101,36 @@ pub trait Logger<I>
     {
         Safe{inner: self, _phantom: PhantomData}
     }
+
+    /// Encode each [Record] with a [Serializer] before forwarding it.
+    ///
+    /// Turns a logger that accepts the serializer's encoded output
+    /// (typically `String` or `Vec<u8>`) into one that accepts rich
+    /// structured records, so a byte sink only needs to be built once.
+    fn serialized<S>(self) -> Serialized<Self, S>
+        where Self: Sized, S: Serializer
+    {
+        Serialized{inner: self, _phantom: PhantomData}
+    }
+
+    /// Apply a fallible function to each input
+    /// before passing it to the logger.
+    fn try_map<F, B>(self, f: F) -> TryMap<Self, F, B>
+        where Self: Sized, F: FnMut(B) -> Result<I, Self::Error>
+    {
+        TryMap{inner: self, f, _phantom: PhantomData}
+    }
+
+    /// Permanently prepend base key-value pairs
+    /// to every [Record] passed through this logger.
+    fn with_fields(self, fields: Vec<(&'static str, Value)>) -> WithFields<Self>
+        where Self: Sized
+    {
+        WithFields{inner: self, fields}
+    }
 }
 
-impl<'a, I, L> Logger<I> for &'a mut L
+impl<I, L> Logger<I> for &mut L
     where L: Logger<I>
 {
     type Error = L::Error;
@@ -84,6 +140,185 @@ impl<'a, I, L> Logger<I> for &'a mut L
     }
 }
 
+/// Spawn `sink` onto a worker thread and return a cheap,
+/// [Clone]-able [Handle] for feeding it from other threads.
+///
+/// Items are pushed over a bounded channel of size `capacity`,
+/// keeping serialization/formatting cost off the caller's hot path;
+/// `policy` decides what happens when that channel is full.
+pub fn async_logger<L, I>(sink: L, capacity: usize, policy: OverflowPolicy) -> (Handle<I>, Guard<I>)
+    where L: Logger<I> + Send + 'static, I: Send + 'static
+{
+    let (sender, receiver) = mpsc::sync_channel(capacity);
+    let join_handle = thread::spawn(move || {
+        let mut sink = sink;
+        for command in receiver {
+            match command {
+                Command::Item(item) => { let _ = sink.log(item); }
+                Command::Flush(ack) => { let _ = ack.send(()); }
+            }
+        }
+    });
+    let handle = Handle{sender: sender.clone(), policy, dropped: Arc::new(AtomicU64::new(0))};
+    let guard = Guard{sender: Some(sender), join_handle: Some(join_handle)};
+    (handle, guard)
+}
+
+/// Error returned by [Handle::log].
+#[derive(Debug)]
+pub enum AsyncError
+{
+    /// The worker thread has shut down and is no longer receiving items.
+    Closed,
+    /// The item was dropped because the channel was full; carries the
+    /// total number of items dropped by this handle (and its clones) so far.
+    Dropped(u64),
+}
+
+/// An item sent down [async_logger]'s channel, or a request to flush it.
+enum Command<I>
+{
+    Item(I),
+    Flush(mpsc::Sender<()>),
+}
+
+/// Drop-guard returned alongside the [Handle] from [async_logger].
+pub struct Guard<I>
+{
+    sender: Option<SyncSender<Command<I>>>,
+    join_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl<I> Guard<I>
+{
+    /// Block until every item sent so far has been written.
+    pub fn flush(&self)
+    {
+        let (ack_sender, ack_receiver) = mpsc::channel();
+        if let Some(sender) = &self.sender {
+            if sender.send(Command::Flush(ack_sender)).is_ok() {
+                let _ = ack_receiver.recv();
+            }
+        }
+    }
+
+    /// Block until the worker thread has drained its queue and exited.
+    pub fn join(mut self) -> thread::Result<()>
+    {
+        self.sender.take();
+        self.join_handle.take().unwrap().join()
+    }
+}
+
+impl<I> Drop for Guard<I>
+{
+    fn drop(&mut self)
+    {
+        self.sender.take();
+        if let Some(join_handle) = self.join_handle.take() {
+            let _ = join_handle.join();
+        }
+    }
+}
+
+/// Cloneable handle returned from [async_logger].
+pub struct Handle<I>
+{
+    sender: SyncSender<Command<I>>,
+    policy: OverflowPolicy,
+    dropped: Arc<AtomicU64>,
+}
+
+impl<I> Clone for Handle<I>
+{
+    fn clone(&self) -> Self
+    {
+        Handle{sender: self.sender.clone(), policy: self.policy, dropped: Arc::clone(&self.dropped)}
+    }
+}
+
+impl<I> Logger<I> for Handle<I>
+    where I: Send + 'static
+{
+    type Error = AsyncError;
+    fn log(&mut self, item: I) -> Result<(), Self::Error>
+    {
+        match self.policy {
+            OverflowPolicy::Block =>
+                self.sender.send(Command::Item(item)).map_err(|_| AsyncError::Closed),
+            OverflowPolicy::Drop =>
+                match self.sender.try_send(Command::Item(item)) {
+                    Ok(()) => Ok(()),
+                    Err(TrySendError::Full(_)) => {
+                        let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                        Err(AsyncError::Dropped(dropped))
+                    }
+                    Err(TrySendError::Disconnected(_)) => Err(AsyncError::Closed),
+                },
+        }
+    }
+}
+
+/// Back-pressure policy used by [Handle::log] when the bounded
+/// channel to the worker thread is full.
+#[derive(Clone, Copy, Debug)]
+pub enum OverflowPolicy
+{
+    /// Block the caller until the worker thread frees up space.
+    Block,
+    /// Drop the item and report how many items have been dropped
+    /// so far through [AsyncError::Dropped].
+    Drop,
+}
+
+/// Return a logger that fans out each item by reference
+/// to every logger in `loggers`.
+pub fn broadcast<L, I>(loggers: Vec<L>) -> Broadcast<L>
+    where L: LogRef<I>
+{
+    Broadcast{loggers}
+}
+
+/// Returned from the [broadcast](fn.broadcast.html) function.
+pub struct Broadcast<L>
+{
+    loggers: Vec<L>,
+}
+
+impl<I, L> Logger<I> for Broadcast<L>
+    where L: LogRef<I>
+{
+    type Error = L::Error;
+    fn log(&mut self, item: I) -> Result<(), Self::Error>
+    {
+        for logger in &mut self.loggers {
+            logger.log_ref(&item)?;
+        }
+        Ok(())
+    }
+}
+
+/// A logger that can log a value by reference instead of by value.
+///
+/// A blanket implementation covers any [Logger] whose item is
+/// [Clone]; sinks that can work from a reference directly may
+/// implement this trait themselves for a zero-copy tee.
+pub trait LogRef<I>
+{
+    type Error;
+    fn log_ref(&mut self, item: &I) -> Result<(), Self::Error>;
+}
+
+impl<I, L> LogRef<I> for L
+    where L: Logger<I>, I: Clone
+{
+    type Error = L::Error;
+    fn log_ref(&mut self, item: &I) -> Result<(), Self::Error>
+    {
+        Logger::log(self, item.clone())
+    }
+}
+
 /// Returned from the [Logger::chain](trait.Logger.html#method.chain) method.
 pub struct Chain<L, M>
 {
@@ -169,6 +404,64 @@ impl<I, L, F> Logger<I> for Filter<L, F>
     }
 }
 
+/// A severity level, ordered from most to least severe.
+///
+/// [Level::Critical] is the most severe level and [Level::Trace]
+/// is the least; the derived ordering is what [Logger::leveled]
+/// uses to decide whether an item passes its threshold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level
+{
+    Critical,
+    Error,
+    Warning,
+    Info,
+    Debug,
+    Trace,
+}
+
+/// Returned from the [Logger::leveled](trait.Logger.html#method.leveled) method.
+pub struct Leveled<L>
+{
+    inner: L,
+    global: Cell<Level>,
+    contexts: RefCell<HashMap<&'static str, Level>>,
+}
+
+impl<L> Leveled<L>
+{
+    /// Change the global threshold used for contexts
+    /// that have no override of their own.
+    pub fn set_level(&self, level: Level)
+    {
+        self.global.set(level);
+    }
+
+    /// Override the threshold for a single context.
+    pub fn set_context_level(&self, context: &'static str, level: Level)
+    {
+        self.contexts.borrow_mut().insert(context, level);
+    }
+}
+
+impl<P, L> Logger<(&'static str, Level, P)> for Leveled<L>
+    where L: Logger<P>
+{
+    type Error = L::Error;
+    fn log(&mut self, (context, level, payload): (&'static str, Level, P)) -> Result<(), Self::Error>
+    {
+        let threshold = match self.contexts.borrow().get(context) {
+            Some(&override_level) => override_level,
+            None => self.global.get(),
+        };
+        if level <= threshold {
+            self.inner.log(payload)
+        } else {
+            Ok(())
+        }
+    }
+}
+
 /// Returned from the [Logger::map](trait.Logger.html#method.map) method.
 pub struct Map<L, F, B>
 {
@@ -188,6 +481,35 @@ impl<I, L, F, B> Logger<B> for Map<L, F, B>
     }
 }
 
+/// Returned from the [Logger::map_err](trait.Logger.html#method.map_err) method.
+pub struct MapErr<L, F>
+{
+    inner: L,
+    f: F,
+}
+
+impl<I, L, F, E> Logger<I> for MapErr<L, F>
+    where L: Logger<I>, F: FnMut(L::Error) -> E
+{
+    type Error = E;
+    fn log(&mut self, item: I) -> Result<(), Self::Error>
+    {
+        self.inner.log(item).map_err(&mut self.f)
+    }
+}
+
+/// A log message together with an ordered list of key-value pairs.
+///
+/// Unlike a pre-formatted string, the fields are kept structured
+/// so that a [Serializer] can decide how to render them (as JSON,
+/// as logfmt, or otherwise) rather than baking that decision into
+/// the message itself.
+pub struct Record
+{
+    pub message: String,
+    pub fields: Vec<(&'static str, Value)>,
+}
+
 /// Returned from the [Logger::safe](trait.Logger.html#method.safe) method.
 pub struct Safe<L, E>
 {
@@ -207,10 +529,180 @@ impl<I, L, E> Logger<I> for Safe<L, E>
     }
 }
 
+/// Encodes the key-value pairs of a [Record] into some output,
+/// mirroring slog's structured/machine-readable serializer model.
+///
+/// A fresh serializer is created for each record, fed the message
+/// and fields in order, then consumed by [finish](Serializer::finish)
+/// to produce the encoded output (typically `String` or `Vec<u8>`).
+pub trait Serializer: Default
+{
+    type Output;
+    type Error;
+    fn emit_message(&mut self, message: &str) -> Result<(), Self::Error>;
+    fn emit_str(&mut self, key: &'static str, value: &str) -> Result<(), Self::Error>;
+    fn emit_bool(&mut self, key: &'static str, value: bool) -> Result<(), Self::Error>;
+    fn emit_i64(&mut self, key: &'static str, value: i64) -> Result<(), Self::Error>;
+    fn emit_u64(&mut self, key: &'static str, value: u64) -> Result<(), Self::Error>;
+    fn emit_f64(&mut self, key: &'static str, value: f64) -> Result<(), Self::Error>;
+    fn finish(self) -> Result<Self::Output, Self::Error>;
+}
+
+/// Returned from the [Logger::serialized](trait.Logger.html#method.serialized) method.
+pub struct Serialized<L, S>
+{
+    inner: L,
+    _phantom: PhantomData<fn() -> S>,
+}
+
+impl<L, S> Logger<Record> for Serialized<L, S>
+    where L: Logger<S::Output, Error=S::Error>, S: Serializer
+{
+    type Error = S::Error;
+    fn log(&mut self, item: Record) -> Result<(), Self::Error>
+    {
+        let mut serializer = S::default();
+        serializer.emit_message(&item.message)?;
+        for (key, value) in item.fields {
+            match value {
+                Value::Str(value) => serializer.emit_str(key, &value)?,
+                Value::Bool(value) => serializer.emit_bool(key, value)?,
+                Value::I64(value) => serializer.emit_i64(key, value)?,
+                Value::U64(value) => serializer.emit_u64(key, value)?,
+                Value::F64(value) => serializer.emit_f64(key, value)?,
+            }
+        }
+        let output = serializer.finish()?;
+        self.inner.log(output)
+    }
+}
+
+/// Returned from the [Logger::try_map](trait.Logger.html#method.try_map) method.
+pub struct TryMap<L, F, B>
+{
+    inner: L,
+    f: F,
+    _phantom: PhantomData<fn() -> B>,
+}
+
+impl<I, L, F, B> Logger<B> for TryMap<L, F, B>
+    where L: Logger<I>, F: FnMut(B) -> Result<I, L::Error>
+{
+    type Error = L::Error;
+    fn log(&mut self, item: B) -> Result<(), Self::Error>
+    {
+        let new_item = (self.f)(item)?;
+        self.inner.log(new_item)
+    }
+}
+
+/// A value attached to a [Record] as part of a key-value pair.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value
+{
+    Str(String),
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+}
+
+/// Returned from the [Logger::with_fields](trait.Logger.html#method.with_fields) method.
+pub struct WithFields<L>
+{
+    inner: L,
+    fields: Vec<(&'static str, Value)>,
+}
+
+impl<L> Logger<Record> for WithFields<L>
+    where L: Logger<Record>
+{
+    type Error = L::Error;
+    fn log(&mut self, mut item: Record) -> Result<(), Self::Error>
+    {
+        let mut fields = self.fields.clone();
+        fields.append(&mut item.fields);
+        item.fields = fields;
+        self.inner.log(item)
+    }
+}
+
 #[cfg(test)]
 mod tests
 {
     use super::*;
+    use std::rc::Rc;
+    use std::sync::Mutex;
+
+    struct SharedVec(Arc<Mutex<Vec<i32>>>);
+
+    impl Logger<i32> for SharedVec
+    {
+        type Error = Infallible;
+        fn log(&mut self, item: i32) -> Result<(), Self::Error>
+        {
+            self.0.lock().unwrap().push(item);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_async_logger()
+    {
+        let container = Arc::new(Mutex::new(Vec::new()));
+        let sink = SharedVec(Arc::clone(&container));
+        let (mut handle, guard) = async_logger(sink, 4, OverflowPolicy::Block);
+        handle.log(0).unwrap();
+        handle.log(1).unwrap();
+        guard.flush();
+        assert_eq!(&*container.lock().unwrap(), &[0, 1]);
+        drop(handle);
+        guard.join().unwrap();
+    }
+
+    #[test]
+    fn test_broadcast()
+    {
+        let mut fst = extender(Vec::new());
+        let mut snd = extender(Vec::new());
+        let mut thd = broadcast(vec![fst.by_ref(), snd.by_ref()]);
+        thd.log(vec![0, 1]).unwrap();
+        assert_eq!(&fst.container, &[vec![0, 1]]);
+        assert_eq!(&snd.container, &[vec![0, 1]]);
+    }
+
+    struct FieldCounter(Rc<Cell<usize>>);
+
+    impl LogRef<Record> for FieldCounter
+    {
+        type Error = Infallible;
+        fn log_ref(&mut self, item: &Record) -> Result<(), Self::Error>
+        {
+            self.0.set(self.0.get() + item.fields.len());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_broadcast_zero_copy()
+    {
+        // Record is not Clone, so this only compiles because each
+        // FieldCounter below implements LogRef directly and reads
+        // the broadcast item by reference instead of cloning it.
+        let fst_count = Rc::new(Cell::new(0));
+        let snd_count = Rc::new(Cell::new(0));
+        let mut thd = broadcast(vec![
+            FieldCounter(Rc::clone(&fst_count)),
+            FieldCounter(Rc::clone(&snd_count)),
+        ]);
+        let record = Record{
+            message: "hello".to_string(),
+            fields: vec![("a", Value::I64(1)), ("b", Value::I64(2))],
+        };
+        thd.log(record).unwrap();
+        assert_eq!(fst_count.get(), 2);
+        assert_eq!(snd_count.get(), 2);
+    }
 
     #[test]
     fn test_chain()
@@ -234,6 +726,19 @@ mod tests
         assert_eq!(&fst.container, &[0, 1]);
     }
 
+    #[test]
+    fn test_leveled()
+    {
+        let mut fst = extender(Vec::new());
+        let mut snd = fst.by_ref().leveled(Level::Warning);
+        snd.log(("db", Level::Info, 0)).unwrap();
+        snd.log(("db", Level::Error, 1)).unwrap();
+        snd.set_context_level("db", Level::Info);
+        snd.log(("db", Level::Info, 2)).unwrap();
+        snd.log(("other", Level::Info, 3)).unwrap();
+        assert_eq!(&fst.container, &[1, 2]);
+    }
+
     #[test]
     fn test_map()
     {
@@ -244,4 +749,98 @@ mod tests
         snd.log(1).unwrap();
         assert_eq!(&fst.container, &[1, 0, 1]);
     }
+
+    #[test]
+    fn test_map_err()
+    {
+        let mut fst = extender(Vec::new());
+        let mut snd = fst.by_ref().map_err(|never: Infallible| match never {});
+        snd.log(0).unwrap();
+        assert_eq!(&fst.container, &[0]);
+    }
+
+    #[derive(Default)]
+    struct Logfmt
+    {
+        out: String,
+    }
+
+    impl Serializer for Logfmt
+    {
+        type Output = String;
+        type Error = Infallible;
+        fn emit_message(&mut self, message: &str) -> Result<(), Self::Error>
+        {
+            self.out.push_str("msg=\"");
+            self.out.push_str(message);
+            self.out.push('"');
+            Ok(())
+        }
+        fn emit_str(&mut self, key: &'static str, value: &str) -> Result<(), Self::Error>
+        {
+            self.out.push_str(&format!(" {}=\"{}\"", key, value));
+            Ok(())
+        }
+        fn emit_bool(&mut self, key: &'static str, value: bool) -> Result<(), Self::Error>
+        {
+            self.out.push_str(&format!(" {}={}", key, value));
+            Ok(())
+        }
+        fn emit_i64(&mut self, key: &'static str, value: i64) -> Result<(), Self::Error>
+        {
+            self.out.push_str(&format!(" {}={}", key, value));
+            Ok(())
+        }
+        fn emit_u64(&mut self, key: &'static str, value: u64) -> Result<(), Self::Error>
+        {
+            self.out.push_str(&format!(" {}={}", key, value));
+            Ok(())
+        }
+        fn emit_f64(&mut self, key: &'static str, value: f64) -> Result<(), Self::Error>
+        {
+            self.out.push_str(&format!(" {}={}", key, value));
+            Ok(())
+        }
+        fn finish(self) -> Result<Self::Output, Self::Error>
+        {
+            Ok(self.out)
+        }
+    }
+
+    #[test]
+    fn test_serialized()
+    {
+        let mut fst = extender(Vec::new());
+        let mut snd = fst.by_ref().serialized::<Logfmt>();
+        let record = Record{
+            message: "hello".to_string(),
+            fields: vec![("n", Value::I64(1))],
+        };
+        snd.log(record).unwrap();
+        assert_eq!(&fst.container, &["msg=\"hello\" n=1".to_string()]);
+    }
+
+    #[test]
+    fn test_try_map()
+    {
+        let mut fst = extender(Vec::new());
+        let mut snd = fst.by_ref().safe::<&'static str>()
+            .try_map(|s: &str| s.parse::<i32>().map_err(|_| "parse error"));
+        snd.log("1").unwrap();
+        assert_eq!(snd.log("nope"), Err("parse error"));
+        assert_eq!(&fst.container, &[1]);
+    }
+
+    #[test]
+    fn test_with_fields()
+    {
+        let mut fst = extender(Vec::new());
+        let mut snd = fst.by_ref().with_fields(vec![("module", Value::Str("db".to_string()))]);
+        let record = Record{message: "ok".to_string(), fields: vec![("n", Value::I64(1))]};
+        snd.log(record).unwrap();
+        assert_eq!(
+            fst.container[0].fields,
+            vec![("module", Value::Str("db".to_string())), ("n", Value::I64(1))],
+        );
+    }
 }
@@ -0,0 +1,127 @@
+//! Bridge to the standard [log] crate's facade.
+//!
+//! This lets a contralog pipeline become the global logger used
+//! by the whole ecosystem of crates that log through `log::info!`,
+//! `log::error!`, and friends, rather than requiring every caller
+//! to adopt contralog's own API.
+
+use std::sync::Mutex;
+
+use log::Level as LogLevel;
+use log::LevelFilter;
+use log::Log;
+use log::Metadata;
+use log::Record as LogRecord;
+use log::SetLoggerError;
+
+use crate::Level;
+use crate::Logger;
+use crate::Record;
+use crate::Value;
+
+/// Adapts a [Logger]`<`[Record]`>` pipeline into the standard [log::Log] trait.
+///
+/// Wrapped in a [Mutex] because [log::Log] requires `Sync`,
+/// whereas [Logger::log] takes `&mut self`.
+pub struct LogBridge<L>
+{
+    inner: Mutex<L>,
+    max_level: Level,
+}
+
+impl<L> Log for LogBridge<L>
+    where L: Logger<Record> + Send
+{
+    fn enabled(&self, metadata: &Metadata) -> bool
+    {
+        from_log_level(metadata.level()) <= self.max_level
+    }
+
+    fn log(&self, record: &LogRecord)
+    {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let item = Record{
+            message: record.args().to_string(),
+            fields: vec![
+                ("level", Value::Str(format!("{:?}", from_log_level(record.level())))),
+                ("target", Value::Str(record.target().to_string())),
+                ("module_path", Value::Str(record.module_path().unwrap_or("").to_string())),
+                ("file", Value::Str(record.file().unwrap_or("").to_string())),
+                ("line", Value::U64(u64::from(record.line().unwrap_or(0)))),
+            ],
+        };
+        let mut inner = self.inner.lock().unwrap();
+        let _ = inner.log(item);
+    }
+
+    fn flush(&self)
+    {
+    }
+}
+
+/// Install `inner` as the global logger for the [log] facade.
+///
+/// Items with a level less severe than `max_level` are dropped
+/// before ever reaching `inner`.
+pub fn init<L>(inner: L, max_level: Level) -> Result<(), SetLoggerError>
+    where L: Logger<Record> + Send + 'static
+{
+    log::set_boxed_logger(Box::new(LogBridge{inner: Mutex::new(inner), max_level}))?;
+    log::set_max_level(to_level_filter(max_level));
+    Ok(())
+}
+
+fn from_log_level(level: LogLevel) -> Level
+{
+    match level {
+        LogLevel::Error => Level::Error,
+        LogLevel::Warn => Level::Warning,
+        LogLevel::Info => Level::Info,
+        LogLevel::Debug => Level::Debug,
+        LogLevel::Trace => Level::Trace,
+    }
+}
+
+fn to_level_filter(level: Level) -> LevelFilter
+{
+    match level {
+        Level::Critical | Level::Error => LevelFilter::Error,
+        Level::Warning => LevelFilter::Warn,
+        Level::Info => LevelFilter::Info,
+        Level::Debug => LevelFilter::Debug,
+        Level::Trace => LevelFilter::Trace,
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::extender;
+
+    #[test]
+    fn test_log_bridge()
+    {
+        let bridge = LogBridge{
+            inner: Mutex::new(extender(Vec::new())),
+            max_level: Level::Info,
+        };
+        let record = LogRecord::builder()
+            .level(LogLevel::Warn)
+            .target("some::target")
+            .args(format_args!("hello"))
+            .build();
+        bridge.log(&record);
+        let record = LogRecord::builder()
+            .level(LogLevel::Debug)
+            .target("some::target")
+            .args(format_args!("too quiet"))
+            .build();
+        bridge.log(&record);
+        let logged = bridge.inner.lock().unwrap();
+        assert_eq!(logged.container.len(), 1);
+        assert_eq!(logged.container[0].message, "hello");
+    }
+}